@@ -0,0 +1,15 @@
+/// A user-triggered command, fired from either the tray menu or a global
+/// hotkey, and handled by the single dispatch loop in `app`. Kept `Send`
+/// (plain `String`s, no `Rc`) since it has to cross from an OS thread (the
+/// tray/hotkey event loops) into the app's event loop.
+///
+/// `Lock`/`Unlock` carry `None` when fired from a hotkey, meaning "whichever
+/// vehicle is currently active" rather than a specific one picked from a
+/// tray submenu.
+#[derive(Clone, Debug)]
+pub enum Action {
+    Lock(Option<String>),
+    Unlock(Option<String>),
+    Open(String),
+    ToggleVisibility,
+}
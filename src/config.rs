@@ -0,0 +1,34 @@
+use crate::hotkey::Hotkeys;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// Non-sensitive app settings persisted to the platform config directory
+/// (the session token itself lives in the OS keyring, see [`crate::session`]).
+#[derive(Default, Serialize, Deserialize)]
+pub struct Config {
+    pub region: Option<String>,
+    pub hotkeys: Option<Hotkeys>,
+}
+
+fn path() -> Option<std::path::PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "carlink")?;
+    Some(dirs.config_dir().join("config.json"))
+}
+
+/// Loads the persisted config, falling back to defaults if it doesn't exist
+/// yet or can't be parsed.
+pub fn load() -> Config {
+    path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Writes `config` to the platform config directory, creating it if needed.
+pub fn save(config: &Config) -> io::Result<()> {
+    let path = path().ok_or_else(|| io::Error::other("no config directory"))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(config)?)
+}
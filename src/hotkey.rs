@@ -0,0 +1,100 @@
+use crate::{command::Action, config};
+use global_hotkey::hotkey::{Code, HotKey, Modifiers};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, io};
+
+/// User-configurable chords for the global shortcuts, stored alongside the
+/// rest of the app config so the shortcuts keep working even while the
+/// window is hidden.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Hotkeys {
+    pub toggle_visibility: String,
+    pub lock: String,
+    pub unlock: String,
+}
+
+impl Default for Hotkeys {
+    fn default() -> Self {
+        Self {
+            toggle_visibility: "Ctrl+Alt+C".into(),
+            lock: "Ctrl+Alt+L".into(),
+            unlock: "Ctrl+Alt+U".into(),
+        }
+    }
+}
+
+/// Loads the user's configured chords, falling back to [`Hotkeys::default`].
+pub fn load() -> Hotkeys {
+    config::load().hotkeys.unwrap_or_default()
+}
+
+/// Persists `hotkeys` into the app config, used by the settings view when
+/// the user rebinds a shortcut.
+pub fn save(hotkeys: &Hotkeys) -> io::Result<()> {
+    let mut config = config::load();
+    config.hotkeys = Some(hotkeys.clone());
+    config::save(&config)
+}
+
+/// Parses a keystring like `"Ctrl+Alt+L"` into a [`HotKey`]. Only plain
+/// letter/digit keys are supported, which covers every default binding and
+/// anything a user is likely to type into the settings view.
+pub fn parse_chord(chord: &str) -> Option<HotKey> {
+    let mut modifiers = Modifiers::empty();
+    let mut code = None;
+
+    for part in chord.split('+').map(str::trim) {
+        match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => modifiers |= Modifiers::CONTROL,
+            "alt" | "option" => modifiers |= Modifiers::ALT,
+            "shift" => modifiers |= Modifiers::SHIFT,
+            "super" | "cmd" | "meta" | "win" => modifiers |= Modifiers::SUPER,
+            key => code = Some(parse_key_code(key)?),
+        }
+    }
+
+    Some(HotKey::new(Some(modifiers), code?))
+}
+
+fn parse_key_code(key: &str) -> Option<Code> {
+    if let Some(letter) = key.strip_prefix(' ').or(Some(key)).filter(|s| s.len() == 1) {
+        let ch = letter.chars().next()?.to_ascii_uppercase();
+        if ch.is_ascii_alphabetic() {
+            return Code::from_char(ch);
+        }
+        if ch.is_ascii_digit() {
+            return Code::from_char(ch);
+        }
+    }
+    None
+}
+
+/// Builds the set of global hotkeys from `hotkeys`, registers them with
+/// `manager`, and returns the id -> [`Action`] mapping the event loop uses to
+/// turn a fired `GlobalHotKeyEvent` back into a command.
+pub fn register(
+    manager: &global_hotkey::GlobalHotKeyManager,
+    hotkeys: &Hotkeys,
+) -> HashMap<u32, Action> {
+    let mut actions = HashMap::new();
+
+    let bindings = [
+        (&hotkeys.toggle_visibility, Action::ToggleVisibility),
+        (&hotkeys.lock, Action::Lock(None)),
+        (&hotkeys.unlock, Action::Unlock(None)),
+    ];
+
+    for (chord, action) in bindings {
+        let Some(hot_key) = parse_chord(chord) else {
+            log::warn!("failed to parse hotkey chord: {chord}");
+            continue;
+        };
+        if let Err(err) = manager.register(hot_key) {
+            log::warn!("failed to register hotkey {chord}: {err}");
+            continue;
+        }
+        actions.insert(hot_key.id(), action);
+    }
+
+    actions
+}
@@ -1,4 +1,4 @@
-use car_api::{Client, Vehicle};
+use car_api::{Client, Region, Vehicle};
 use dioxus::prelude::*;
 use dioxus_desktop::{
     tao::{dpi::PhysicalPosition, window},
@@ -10,8 +10,10 @@ use fermi::{use_init_atom_root, use_read, use_set, Atom};
 use image::ImageFormat;
 use log::LevelFilter;
 use std::{
+    collections::HashMap,
     io::{BufReader, Cursor, IoSlice, Read},
     rc::Rc,
+    sync::{Arc, Mutex, OnceLock},
     thread,
 };
 use tokio::sync::mpsc;
@@ -20,6 +22,15 @@ use tray_icon::{
     Icon, TrayIconBuilder, TrayIconEvent,
 };
 
+mod command;
+mod config;
+mod hotkey;
+mod region;
+mod session;
+mod stream;
+mod sync;
+mod tray;
+
 const _: &str = manganis::font!({ families: ["Roboto"] });
 
 fn load_icon() -> tray_icon::Icon {
@@ -44,12 +55,14 @@ async fn main() {
 
     log::info!("starting app");
 
-    let config = Config::new().with_window(
-        WindowBuilder::new()
-            .with_resizable(false)
-            .with_inner_size(PhysicalSize::new(400., 400.))
-            .with_decorations(false).with_visible(false),
-    );
+    let config = Config::new()
+        .with_window(
+            WindowBuilder::new()
+                .with_resizable(false)
+                .with_inner_size(PhysicalSize::new(400., 400.))
+                .with_decorations(false).with_visible(false),
+        )
+        .with_custom_protocol("carstream", stream::handler(stream_client_cell()));
     dioxus_desktop::launch_cfg(app, config);
 }
 
@@ -76,11 +89,94 @@ fn app(cx: Scope) -> Element {
         }
     });
 
-    to_owned![tx];
+    let client = use_read(cx, &CLIENT).clone();
+    let region = *use_read(cx, &REGION);
+    let session_id = use_read(cx, &SESSION_ID).clone();
+    let vehicles = use_read(cx, &VEHICLES).clone();
+    let vehicle_state = use_read(cx, &sync::VEHICLE_STATE).clone();
+    let sync_tx = use_read(cx, &SYNC_TX).clone();
+
+    let actions = use_signal(cx, || Arc::new(Mutex::new(HashMap::new())) as tray::ActionMap);
+
+    let active_vehicle = use_read(cx, &ACTIVE_VEHICLE).clone();
+
+    // `use_future` below only runs its init closure once (deps are `()`), so
+    // it can't just capture these by value — that snapshot would freeze at
+    // whatever the atoms held on `app`'s first render, which for
+    // `session_id`/`sync_tx` is always `None` (set only after login). Each
+    // cell is kept current by the `use_effect` further down and read live on
+    // every loop iteration instead.
+    let active_vehicle_cell = use_signal(cx, || Arc::new(Mutex::new(None::<String>)));
+    let client_cell = use_signal(cx, || Arc::new(Mutex::new(Rc::new(Client::us()))));
+    let session_id_cell = use_signal(cx, || Arc::new(Mutex::new(None::<String>)));
+    let sync_tx_cell = use_signal(cx, || Arc::new(Mutex::new(None::<mpsc::Sender<sync::Optimistic>>)));
+
+    let action_channel = use_signal(cx, || {
+        let (tx, rx) = mpsc::unbounded_channel::<command::Action>();
+        (tx, RefCell::new(Some(rx)))
+    });
+    let (action_tx, action_rx) = &*action_channel();
+
+    let set_open_request = use_set(cx, &OPEN_REQUEST).clone();
+
+    to_owned![
+        window,
+        active_vehicle_cell,
+        client_cell,
+        session_id_cell,
+        sync_tx_cell,
+        set_open_request
+    ];
+    use_future(cx, (), move |_| {
+        let mut action_rx = action_rx.borrow_mut().take().unwrap();
+        async move {
+            while let Some(action) = action_rx.recv().await {
+                let (vehicle_key, is_locked) = match action {
+                    command::Action::ToggleVisibility => {
+                        window.set_visible(!window.is_visible());
+                        continue;
+                    }
+                    command::Action::Open(vehicle_key) => {
+                        window.set_visible(true);
+                        set_open_request(Some(vehicle_key));
+                        continue;
+                    }
+                    command::Action::Lock(vehicle_key) => {
+                        (vehicle_key.or_else(|| active_vehicle_cell().lock().unwrap().clone()), true)
+                    }
+                    command::Action::Unlock(vehicle_key) => {
+                        (vehicle_key.or_else(|| active_vehicle_cell().lock().unwrap().clone()), false)
+                    }
+                };
+
+                let session_id = session_id_cell().lock().unwrap().clone();
+                let (Some(session_id), Some(vehicle_key)) = (session_id, vehicle_key) else {
+                    continue;
+                };
+
+                let client = client_cell().lock().unwrap().clone();
+                if is_locked {
+                    client.lock(&session_id, &vehicle_key).await;
+                } else {
+                    client.unlock(&session_id, &vehicle_key).await;
+                }
+
+                let sync_tx = sync_tx_cell().lock().unwrap().clone();
+                if let Some(sync_tx) = sync_tx {
+                    let _ = sync_tx
+                        .send(sync::Optimistic { vehicle_key, is_locked })
+                        .await;
+                }
+            }
+        }
+    });
+
+    to_owned![tx, action_tx];
+    let actions_for_thread = actions().clone();
     let tray_icon = use_signal(cx, || {
-        let menu = Menu::new();
+        let menu = tray::build(&[], &HashMap::new(), &actions_for_thread);
         let tray_icon = TrayIconBuilder::new()
-            .with_tooltip("system-tray - tray icon library!")
+            .with_tooltip("carlink")
             .with_menu(Box::new(menu))
             .with_icon(load_icon())
             .build()
@@ -88,10 +184,13 @@ fn app(cx: Scope) -> Element {
 
         let menu_channel = MenuEvent::receiver();
         let tray_channel = TrayIconEvent::receiver();
+        let actions = actions_for_thread;
 
         thread::spawn(move || loop {
             if let Ok(event) = menu_channel.try_recv() {
-                println!("{event:?}");
+                if let Some(action) = actions.lock().unwrap().get(&event.id).cloned() {
+                    let _ = action_tx.send(action);
+                }
             }
 
             if let Ok(event) = tray_channel.try_recv() {
@@ -100,13 +199,64 @@ fn app(cx: Scope) -> Element {
                     event.icon_rect.bottom,
                 ))
                 .unwrap();
-                println!("{event:?}");
             }
         });
 
         tray_icon
     });
 
+    to_owned![action_tx];
+    use_signal(cx, || {
+        let manager = global_hotkey::GlobalHotKeyManager::new().unwrap();
+        let actions = hotkey::register(&manager, &hotkey::load());
+
+        let hotkey_channel = global_hotkey::GlobalHotKeyEvent::receiver();
+
+        thread::spawn(move || loop {
+            if let Ok(event) = hotkey_channel.try_recv() {
+                if let Some(action) = actions.get(&event.id) {
+                    let _ = action_tx.send(action.clone());
+                }
+            }
+        });
+
+        // Kept alive for the app's lifetime; dropping the manager unregisters
+        // every hotkey.
+        manager
+    });
+
+    use_effect(cx, (&active_vehicle,), move |(active_vehicle,)| {
+        *active_vehicle_cell().lock().unwrap() = active_vehicle;
+        async move {}
+    });
+
+    use_effect(
+        cx,
+        (&client, &session_id, &sync_tx),
+        move |(client, session_id, sync_tx)| {
+            *client_cell().lock().unwrap() = client;
+            *session_id_cell().lock().unwrap() = session_id;
+            *sync_tx_cell().lock().unwrap() = sync_tx;
+            async move {}
+        },
+    );
+
+    // Keeps the `carstream://` handler's client pinned to whichever region
+    // `Login`/`Home` most recently picked, instead of whatever was active
+    // when `main()` registered the protocol handler.
+    use_effect(cx, (&region,), move |(region,)| {
+        *stream_client_cell().lock().unwrap() = Arc::new(Client::for_region(region));
+        async move {}
+    });
+
+    use_effect(cx, (&vehicles, &vehicle_state), move |(vehicles, vehicle_state)| {
+        let vehicles = vehicles.unwrap_or_default();
+        let actions = actions().clone();
+        let menu = tray::build(&vehicles, &vehicle_state, &actions);
+        tray_icon.with_mut(|tray_icon| tray_icon.set_menu(Some(Box::new(menu))));
+        async move {}
+    });
+
     render! { Router::<Route> {} }
 }
 
@@ -121,10 +271,29 @@ enum Route {
     Vehicles,
     #[route("/vehicles/:id")]
     Vehicle { id: String },
+    #[route("/settings")]
+    Settings,
 }
 
 #[component]
 fn Layout(cx: Scope) -> Element {
+    let navigator = use_navigator(cx);
+    let open_request = use_read(cx, &OPEN_REQUEST).clone();
+    let set_open_request = use_set(cx, &OPEN_REQUEST).clone();
+
+    // Mounted for every route (it's the router's `#[layout]`), so this is
+    // where a tray "Open" click - which has no route/navigator of its own,
+    // firing from `app` above the `Router` - gets turned into a navigation.
+    use_effect(cx, (&open_request,), move |(open_request,)| {
+        to_owned![navigator, set_open_request];
+        async move {
+            if let Some(id) = open_request {
+                navigator.push(Route::Vehicle { id });
+                set_open_request(None);
+            }
+        }
+    });
+
     cx.render(rsx! { div { position: "fixed", top: 0, left: 0, width: "100vw", height: "100vh", font: "16px Roboto", color: "#fff", background: "#000",  Outlet::<Route> {} } })
 }
 
@@ -132,6 +301,22 @@ fn Layout(cx: Scope) -> Element {
 fn Home(cx: Scope) -> Element {
     let navigator = use_navigator(cx);
     let session_id = use_read(cx, &SESSION_ID).clone();
+    let set_session_id = use_set(cx, &SESSION_ID).clone();
+    let set_region = use_set(cx, &REGION).clone();
+    let set_client = use_set(cx, &CLIENT).clone();
+
+    use_effect(cx, (), move |_| {
+        to_owned![set_session_id, set_region, set_client];
+        async move {
+            if let Some(region) = session::load_region() {
+                set_client(Rc::new(Client::for_region(region)));
+                set_region(region);
+            }
+            if let Some(session) = session::Session::load() {
+                set_session_id(Some(session.token));
+            }
+        }
+    });
 
     if session_id.is_none() {
         navigator.push(Route::Login);
@@ -145,9 +330,18 @@ fn Home(cx: Scope) -> Element {
 #[component]
 fn Login(cx: Scope) -> Element {
     let client = use_read(cx, &CLIENT);
+    let region = *use_read(cx, &REGION);
     let navigator = use_navigator(cx);
 
     let set_session_id = use_set(cx, &SESSION_ID).clone();
+    let set_region = use_set(cx, &REGION).clone();
+    let set_client = use_set(cx, &CLIENT).clone();
+
+    let region_options = region::ALL.iter().map(|&option| {
+        cx.render(rsx! {
+            option { value: "{region::to_str(option)}", "{region::label(option)}" }
+        })
+    });
 
     cx.render(rsx! {
         form { onsubmit: move |event| {
@@ -156,10 +350,30 @@ fn Login(cx: Scope) -> Element {
                     let session_id = client
                         .login(&event.values["username"][0], &event.values["password"][0])
                         .await;
+
+                    let session = session::Session {
+                        token: session_id.clone(),
+                    };
+                    if let Err(err) = session.save() {
+                        log::warn!("failed to persist session: {err}");
+                    }
+
                     set_session_id(Some(session_id));
                     navigator.push(Route::Vehicles);
                 }
             },
+            select {
+                value: "{region::to_str(region)}",
+                onchange: move |event| {
+                    let Some(region) = region::from_str(&event.value) else { return };
+                    set_client(Rc::new(Client::for_region(region)));
+                    set_region(region);
+                    if let Err(err) = session::save_region(region) {
+                        log::warn!("failed to persist region: {err}");
+                    }
+                },
+                region_options
+            }
             input { r#type: "text", name: "username" }
             input { r#type: "password", name: "password" }
             input { r#type: "submit" }
@@ -169,22 +383,88 @@ fn Login(cx: Scope) -> Element {
 
 static CLIENT: Atom<Rc<Client>> = Atom(|_| Rc::new(Client::us()));
 
+/// Mirrors `CLIENT`'s region, but reachable from `main()` (before the atom
+/// root exists) and from the `carstream://` protocol handler (a plain wry
+/// callback that runs outside the component tree entirely, so it can't
+/// `use_read` the atom). Holds a separate `Arc<Client>` rather than sharing
+/// `CLIENT`'s `Rc<Client>` since the protocol handler's background poller
+/// needs a `Send` client to hand to `tokio::spawn`; see `stream::handler`.
+static STREAM_CLIENT: OnceLock<Arc<Mutex<Arc<Client>>>> = OnceLock::new();
+
+fn stream_client_cell() -> Arc<Mutex<Arc<Client>>> {
+    STREAM_CLIENT
+        .get_or_init(|| {
+            let region = session::load_region().unwrap_or(Region::Us);
+            Arc::new(Mutex::new(Arc::new(Client::for_region(region))))
+        })
+        .clone()
+}
+
+static REGION: Atom<Region> = Atom(|_| Region::Us);
+
 static SESSION_ID: Atom<Option<String>> = Atom(|_| None);
 
 static VEHICLES: Atom<Option<Vec<Vehicle>>> = Atom(|_| None);
 
+/// Sending half of the vehicle-state sync channel, stashed here once
+/// [`sync::spawn`] runs so the `Vehicle` lock button can push optimistic
+/// updates without threading the channel through the router.
+static SYNC_TX: Atom<Option<mpsc::Sender<sync::Optimistic>>> = Atom(|_| None);
+
+/// The vehicle currently open in the `Vehicle` view, used as the target for
+/// a global lock/unlock hotkey fired while the window (and its route) isn't
+/// visible to pick from.
+static ACTIVE_VEHICLE: Atom<Option<String>> = Atom(|_| None);
+
+/// Set by the tray's "Open" action, which has no navigator of its own since
+/// it's dispatched from `app`, above the `Router`; `Layout` watches this and
+/// turns it into an actual route change.
+static OPEN_REQUEST: Atom<Option<String>> = Atom(|_| None);
+
 #[component]
 fn Vehicles(cx: Scope) -> Element {
     let client = use_read(cx, &CLIENT).clone();
     let session_id = use_read(cx, &SESSION_ID).clone();
+    let set_session_id = use_set(cx, &SESSION_ID).clone();
+    let navigator = use_navigator(cx);
 
     let vehicles = use_read(cx, &VEHICLES);
     let set_vehicles = use_set(cx, &VEHICLES).clone();
+    let set_vehicle_state = use_set(cx, &sync::VEHICLE_STATE).clone();
+    let set_sync_tx = use_set(cx, &SYNC_TX).clone();
 
-    use_effect(cx, &session_id, move |session_id| async move {
-        if let Some(session_id) = session_id {
-            let new_vehicles = client.vehicles(&session_id).await;
-            set_vehicles(Some(new_vehicles));
+    use_effect(cx, &session_id, move |session_id| {
+        to_owned![client, navigator, set_session_id];
+        async move {
+            if let Some(session_id) = session_id {
+                // `car_api::Client::vehicles` returns `Vec<Vehicle>` directly,
+                // not a `Result`, so there's no error variant to tell a
+                // rejected restored token apart from a valid one with zero
+                // vehicles. Treat an empty result as a rejected token and
+                // bounce back to `Login` - it's the only signal this API
+                // surface gives us, at the cost of also logging out a real
+                // account that happens to have zero vehicles.
+                let new_vehicles = client.vehicles(&session_id).await;
+                if new_vehicles.is_empty() {
+                    let _ = session::Session::default().clear();
+                    set_session_id(None);
+                    navigator.push(Route::Login);
+                    return;
+                }
+
+                let vehicle_keys = new_vehicles
+                    .iter()
+                    .map(|vehicle| vehicle.vehicle_key.clone())
+                    .collect();
+                let (sync_tx, mut state_rx) = sync::spawn(client, session_id, vehicle_keys);
+                set_sync_tx(Some(sync_tx));
+
+                set_vehicles(Some(new_vehicles));
+
+                while let Some(state) = state_rx.recv().await {
+                    set_vehicle_state(state);
+                }
+            }
         }
     });
 
@@ -207,19 +487,64 @@ fn Vehicles(cx: Scope) -> Element {
         })
     });
 
+    to_owned![navigator, set_session_id];
     cx.render(rsx! {
         h4 { "Vehicles" }
+        button {
+            onclick: move |_| {
+                let _ = session::Session::default().clear();
+                set_session_id(None);
+                navigator.push(Route::Login);
+            },
+            "Log out"
+        }
+        Link { to: Route::Settings, "Settings" }
         vehicle_items
     })
 }
 
+#[component]
+fn Settings(cx: Scope) -> Element {
+    let hotkeys = use_signal(cx, hotkey::load);
+    let saved = use_signal(cx, || false);
+
+    cx.render(rsx! {
+        h4 { "Settings" }
+        form {
+            onsubmit: move |event| {
+                let hotkeys = hotkey::Hotkeys {
+                    toggle_visibility: event.values["toggle_visibility"][0].clone(),
+                    lock: event.values["lock"][0].clone(),
+                    unlock: event.values["unlock"][0].clone(),
+                };
+                saved.set(hotkey::save(&hotkeys).is_ok());
+            },
+            label { "Show/hide window", input { r#type: "text", name: "toggle_visibility", value: "{hotkeys().toggle_visibility}" } }
+            label { "Lock", input { r#type: "text", name: "lock", value: "{hotkeys().lock}" } }
+            label { "Unlock", input { r#type: "text", name: "unlock", value: "{hotkeys().unlock}" } }
+            input { r#type: "submit" }
+        }
+        // Hotkeys are registered once at startup; a saved rebind takes effect
+        // on the next launch.
+        if *saved() { cx.render(rsx! { p { "Saved. Restart carlink to apply." } }) }
+    })
+}
+
 #[component]
 fn Vehicle(cx: Scope, id: String) -> Element {
     let client = use_read(cx, &CLIENT).clone();
     let session_id = use_read(cx, &SESSION_ID).clone();
     let vehicles = use_read(cx, &VEHICLES);
+    let vehicle_state = use_read(cx, &sync::VEHICLE_STATE);
+    let sync_tx = use_read(cx, &SYNC_TX).clone();
+    let set_active_vehicle = use_set(cx, &ACTIVE_VEHICLE).clone();
 
-    let lock = use_signal(cx, || Some(true));
+    let pending = use_signal(cx, || false);
+
+    use_effect(cx, (id,), move |(id,)| {
+        to_owned![set_active_vehicle];
+        async move { set_active_vehicle(Some(id)) }
+    });
 
     if let Some(vehicles) = vehicles {
         let vehicle = vehicles
@@ -227,20 +552,32 @@ fn Vehicle(cx: Scope, id: String) -> Element {
             .find(|vehicle| &vehicle.vehicle_key == id)
             .unwrap();
 
-        let lock_button = if let Some(is_locked) = *lock() {
+        let is_locked = vehicle_state.get(&vehicle.vehicle_key).map(|state| state.is_locked);
+
+        let lock_button = if *pending() {
+            cx.render(rsx! {"Loading..."})
+        } else if let Some(is_locked) = is_locked {
             cx.render(rsx! {
                 button { onclick: move |_| {
-                        lock.set(None);
-                        let vehicle_id = vehicle.vehicle_key.clone();
-                        to_owned![client, session_id];
+                        pending.set(true);
+                        let vehicle_key = vehicle.vehicle_key.clone();
+                        to_owned![client, session_id, sync_tx];
                         async move {
                             let session_id = session_id.as_ref().unwrap();
                             if is_locked {
-                                client.unlock(session_id, &vehicle_id).await;
+                                client.unlock(session_id, &vehicle_key).await;
                             } else {
-                                client.lock(session_id, &vehicle_id).await;
+                                client.lock(session_id, &vehicle_key).await;
+                            }
+                            if let Some(sync_tx) = sync_tx {
+                                let _ = sync_tx
+                                    .send(sync::Optimistic {
+                                        vehicle_key,
+                                        is_locked: !is_locked,
+                                    })
+                                    .await;
                             }
-                            lock.set(Some(!is_locked));
+                            pending.set(false);
                         }
                     },
                     if is_locked { "Unlock" } else { "Lock" }
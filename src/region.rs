@@ -0,0 +1,33 @@
+use car_api::Region;
+
+/// Every market `car_api` knows how to authenticate against, in the order
+/// shown in the `Login` dropdown.
+pub const ALL: [Region; 3] = [Region::Us, Region::Eu, Region::Ca];
+
+/// A human-readable label for the `Login` region dropdown.
+pub fn label(region: Region) -> &'static str {
+    match region {
+        Region::Us => "United States",
+        Region::Eu => "Europe",
+        Region::Ca => "Canada",
+    }
+}
+
+/// A stable identifier for persisting the selected region to disk; `car_api`
+/// doesn't give us `Serialize`, so we round-trip through this instead.
+pub fn to_str(region: Region) -> &'static str {
+    match region {
+        Region::Us => "us",
+        Region::Eu => "eu",
+        Region::Ca => "ca",
+    }
+}
+
+pub fn from_str(value: &str) -> Option<Region> {
+    match value {
+        "us" => Some(Region::Us),
+        "eu" => Some(Region::Eu),
+        "ca" => Some(Region::Ca),
+        _ => None,
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{config, region};
+use car_api::Region;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+const KEYRING_SERVICE: &str = "carlink";
+const KEYRING_USER: &str = "session";
+
+/// A logged-in session, persisted across launches so the user doesn't have
+/// to re-enter the `Login` form every time the app starts.
+///
+/// The session token itself lives in the OS secret store (via `keyring`);
+/// only the non-sensitive region hint is written to the config directory.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Session {
+    pub token: String,
+}
+
+impl Session {
+    /// Loads the persisted session, if any. Returns `None` if the user has
+    /// never logged in or the OS declined to return the stored token.
+    pub fn load() -> Option<Self> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).ok()?;
+        let token = entry.get_password().ok()?;
+        Some(Self { token })
+    }
+
+    /// Writes the session token to the OS secret store.
+    pub fn save(&self) -> Result<(), keyring::Error> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)?;
+        entry.set_password(&self.token)
+    }
+
+    /// Clears the persisted session, used by the "Log out" action.
+    pub fn clear(&self) -> Result<(), keyring::Error> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)?;
+        match entry.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Reads the last-used region, if one was saved alongside the session.
+pub fn load_region() -> Option<Region> {
+    config::load().region.as_deref().and_then(region::from_str)
+}
+
+/// Persists the last-used region into the app config.
+pub fn save_region(selected: Region) -> io::Result<()> {
+    let mut config = config::load();
+    config.region = Some(region::to_str(selected).to_string());
+    config::save(&config)
+}
@@ -0,0 +1,173 @@
+use car_api::Client;
+use http::{
+    header::{ACCEPT_RANGES, CONTENT_RANGE, RANGE},
+    Request, Response,
+};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::mpsc;
+
+/// A single decoded camera frame, shared between the polling task and the
+/// `carstream://` protocol handler.
+#[derive(Clone, Default)]
+struct Frame(Arc<Vec<u8>>);
+
+/// Minimum gap between camera snapshot fetches, so a fast (or fast-failing)
+/// `car_api` response can't turn this into a busy loop hammering the vehicle.
+const FRAME_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Backoff applied after a failed fetch, on top of [`FRAME_INTERVAL`].
+const ERROR_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Spawns a background task that keeps pulling the latest camera frame for
+/// `vehicle_key` from `client` and forwards it over a channel, so the
+/// protocol handler below never blocks the UI thread waiting on the network.
+///
+/// Takes `Arc<Client>` (not `Rc`, unlike the rest of the app) and spawns with
+/// plain `tokio::spawn`: this runs from the `carstream://` protocol handler,
+/// a wry callback invoked outside the component tree, so there's no
+/// `LocalSet` around it for `spawn_local` to rely on the way Dioxus's own
+/// hook machinery provides one.
+fn spawn_frame_feed(client: Arc<Client>, vehicle_key: String) -> mpsc::Receiver<Frame> {
+    let (tx, rx) = mpsc::channel(1);
+
+    tokio::spawn(async move {
+        loop {
+            match client.camera_snapshot(&vehicle_key).await {
+                Ok(bytes) => {
+                    match tx.try_send(Frame(Arc::new(bytes))) {
+                        // A full channel just means the handler hasn't taken the
+                        // queued frame yet; this newly fetched frame is dropped
+                        // (the queued one is kept) rather than block the feed.
+                        Ok(()) | Err(mpsc::error::TrySendError::Full(_)) => {}
+                        // The handler dropped its receiver, most likely because
+                        // the viewed vehicle changed; stop polling this one.
+                        Err(mpsc::error::TrySendError::Closed(_)) => break,
+                    }
+                    tokio::time::sleep(FRAME_INTERVAL).await;
+                }
+                Err(err) => {
+                    log::warn!("failed to fetch camera frame: {err}");
+                    tokio::time::sleep(FRAME_INTERVAL + ERROR_BACKOFF).await;
+                }
+            }
+        }
+    });
+
+    rx
+}
+
+/// Builds the `carstream://vehicle/{vehicle_key}/camera` protocol handler
+/// registered on `dioxus_desktop::Config` so the `Vehicle` view's
+/// `<img>`/`<video>` tags can stream frames straight from `car_api::Client`.
+///
+/// Frames are served out of a small in-memory buffer fed by
+/// [`spawn_frame_feed`] instead of being buffered whole like [`load_icon`]
+/// does for the tray icon, since camera feeds can be arbitrarily large.
+/// `Range` requests are honored so a `<video>` element can seek without
+/// re-downloading the whole asset.
+///
+/// `client` is read fresh from `client_cell` for every new feed instead of
+/// being fixed at startup, so switching region in `Login` is picked up by
+/// the next vehicle that's opened rather than pinning the stream to whatever
+/// region was active when the protocol handler was registered.
+pub fn handler(
+    client_cell: Arc<Mutex<Arc<Client>>>,
+) -> impl Fn(&Request<Vec<u8>>) -> Response<Vec<u8>> + 'static {
+    let state: Rc<RefCell<Option<(String, mpsc::Receiver<Frame>, Frame)>>> =
+        Rc::new(RefCell::new(None));
+
+    move |request| {
+        let vehicle_key = match parse_vehicle_key(request.uri().path()) {
+            Some(key) => key,
+            None => return not_found(),
+        };
+
+        let mut state = state.borrow_mut();
+        if state.as_ref().map(|(key, ..)| key != &vehicle_key).unwrap_or(true) {
+            let client = client_cell.lock().unwrap().clone();
+            let rx = spawn_frame_feed(client, vehicle_key.clone());
+            *state = Some((vehicle_key, rx, Frame::default()));
+        }
+        let (_, rx, latest) = state.as_mut().unwrap();
+        while let Ok(next) = rx.try_recv() {
+            *latest = next;
+        }
+
+        respond(request, &latest.0)
+    }
+}
+
+fn parse_vehicle_key(path: &str) -> Option<String> {
+    let path = path.strip_prefix("/vehicle/")?;
+    let path = path.strip_suffix("/camera")?;
+    Some(path.to_string())
+}
+
+/// Builds a `200`/`206`/`416` response for `body` according to the request's
+/// `Range` header, the same range semantics an HTTP server applies to a
+/// static file.
+fn respond(request: &Request<Vec<u8>>, body: &[u8]) -> Response<Vec<u8>> {
+    let len = body.len();
+
+    let range = request
+        .headers()
+        .get(RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_range(value, len));
+
+    match range {
+        Some(Ok((start, end))) => Response::builder()
+            .status(206)
+            .header(CONTENT_RANGE, format!("bytes {start}-{end}/{len}"))
+            .header(ACCEPT_RANGES, "bytes")
+            .body(body[start..=end].to_vec())
+            .unwrap(),
+        Some(Err(())) => Response::builder()
+            .status(416)
+            .header(CONTENT_RANGE, format!("bytes */{len}"))
+            .body(Vec::new())
+            .unwrap(),
+        None => Response::builder()
+            .status(200)
+            .header(ACCEPT_RANGES, "bytes")
+            .body(body.to_vec())
+            .unwrap(),
+    }
+}
+
+/// Parses a `Range: bytes=a-b` header into an inclusive `(start, end)` byte
+/// range clamped to `len`, or `Err(())` if the range is unsatisfiable.
+fn parse_range(value: &str, len: usize) -> Option<Result<(usize, usize), ()>> {
+    let spec = value.strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+
+    let range = if start.is_empty() {
+        // Suffix form, `-N`: the last N bytes.
+        let suffix_len: usize = end.parse().ok()?;
+        let start = len.saturating_sub(suffix_len);
+        (start, len.saturating_sub(1))
+    } else {
+        let start: usize = start.parse().ok()?;
+        let end = if end.is_empty() {
+            len.saturating_sub(1)
+        } else {
+            end.parse().ok()?
+        };
+        (start, end)
+    };
+
+    if range.0 >= len || range.0 > range.1 {
+        return Some(Err(()));
+    }
+
+    Some(Ok((range.0, range.1.min(len.saturating_sub(1)))))
+}
+
+fn not_found() -> Response<Vec<u8>> {
+    Response::builder().status(404).body(Vec::new()).unwrap()
+}
@@ -0,0 +1,88 @@
+use car_api::Client;
+use fermi::Atom;
+use std::{collections::HashMap, rc::Rc, time::Duration};
+use tokio::sync::mpsc;
+
+/// Snapshot of a single vehicle's live state, as last reported by the sync
+/// poller below.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VehicleState {
+    pub is_locked: bool,
+    pub odometer: f64,
+    /// Fuel level for combustion vehicles, state of charge for EVs; both are
+    /// reported by `car_api` as a 0.0-1.0 fraction.
+    pub fuel_or_charge: f64,
+    pub location: (f64, f64),
+}
+
+/// How often the background poller below checks each vehicle's live state.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Live state for every vehicle on the account, keyed by `vehicle_key`.
+///
+/// Populated from the background poller spawned by [`spawn`]; the `Vehicle`
+/// view reads this instead of faking lock state with a local signal.
+pub static VEHICLE_STATE: Atom<HashMap<String, VehicleState>> = Atom(|_| HashMap::new());
+
+/// An optimistic lock/unlock sent by the UI ahead of the next poll tick, so a
+/// button press isn't immediately overwritten by stale data.
+pub struct Optimistic {
+    pub vehicle_key: String,
+    pub is_locked: bool,
+}
+
+/// Spawns the long-lived poller that keeps the vehicle's live status fresh.
+///
+/// Returns the sending half for optimistic updates (fed from the `Vehicle`
+/// lock button) and the receiving half of the merged state, which the caller
+/// forwards into [`VEHICLE_STATE`] from the `Vehicles` view's `use_effect`.
+pub fn spawn(
+    client: Rc<Client>,
+    session_id: String,
+    vehicle_keys: Vec<String>,
+) -> (mpsc::Sender<Optimistic>, mpsc::Receiver<HashMap<String, VehicleState>>) {
+    let (optimistic_tx, mut optimistic_rx) = mpsc::channel(16);
+    let (state_tx, state_rx) = mpsc::channel(16);
+
+    tokio::task::spawn_local(async move {
+        let mut state: HashMap<String, VehicleState> = HashMap::new();
+        // Counts down while a vehicle's optimistic update should win over the
+        // next poll tick(s), so a stale in-flight request can't clobber it.
+        // Decremented (and cleared at zero) every tick below so polling always
+        // resumes after skipping the one tick right after an optimistic update.
+        let mut debounced: HashMap<String, u8> = HashMap::new();
+
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(POLL_INTERVAL) => {
+                    for vehicle_key in &vehicle_keys {
+                        if let Some(count) = debounced.get_mut(vehicle_key) {
+                            *count -= 1;
+                            if *count == 0 {
+                                debounced.remove(vehicle_key);
+                            }
+                            continue;
+                        }
+                        match client.status(&session_id, vehicle_key).await {
+                            Ok(status) => {
+                                state.insert(vehicle_key.clone(), status.into());
+                                let _ = state_tx.send(state.clone()).await;
+                            }
+                            Err(err) => log::warn!("failed to poll {vehicle_key}: {err}"),
+                        }
+                    }
+                }
+                Some(update) = optimistic_rx.recv() => {
+                    // Skip exactly the next tick for this vehicle.
+                    debounced.insert(update.vehicle_key.clone(), 1);
+                    state
+                        .entry(update.vehicle_key)
+                        .and_modify(|vehicle| vehicle.is_locked = update.is_locked);
+                    let _ = state_tx.send(state.clone()).await;
+                }
+            }
+        }
+    });
+
+    (optimistic_tx, state_rx)
+}
@@ -0,0 +1,75 @@
+use crate::{command::Action, sync::VehicleState};
+use car_api::Vehicle;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tray_icon::{
+    menu::{IconMenuItem, Menu, MenuId, MenuItem, Submenu},
+    Icon,
+};
+
+/// Maps menu item ids back to the vehicle/action they were built for, since
+/// `tray-icon` only reports the clicked `MenuId` in its event, not the
+/// `Action` it was built from. Shared between the rebuild below (runs on the
+/// UI task) and the menu event loop (runs on its own OS thread).
+pub type ActionMap = Arc<Mutex<HashMap<MenuId, Action>>>;
+
+/// A small solid-color square used as the lock item's status icon, since
+/// `tray-icon` only draws menu icons from raw RGBA, not text/emoji.
+fn status_icon(rgba: [u8; 4]) -> Icon {
+    const SIZE: u32 = 8;
+    let pixels = rgba.repeat((SIZE * SIZE) as usize);
+    Icon::from_rgba(pixels, SIZE, SIZE).expect("status icon has valid dimensions")
+}
+
+/// Rebuilds the tray `Menu` from the current vehicle list and synced lock
+/// state, giving each vehicle a submenu with "Lock"/"Unlock" (tagged with a
+/// small status icon) and "Open" entries, and records the id -> action
+/// mapping into `actions` for the event loop to consult.
+pub fn build(
+    vehicles: &[Vehicle],
+    state: &HashMap<String, VehicleState>,
+    actions: &ActionMap,
+) -> Menu {
+    let menu = Menu::new();
+    let mut map = HashMap::new();
+
+    for vehicle in vehicles {
+        let is_locked = state.get(&vehicle.vehicle_key).map(|vehicle| vehicle.is_locked);
+
+        let submenu = Submenu::new(&vehicle.nick_name, true);
+
+        let lock_label = match is_locked {
+            Some(true) => "Unlock",
+            Some(false) => "Lock",
+            None => "Lock/Unlock (syncing...)",
+        };
+        // Red while locked, green while unlocked, gray while the synced state
+        // hasn't arrived yet.
+        let lock_icon = match is_locked {
+            Some(true) => status_icon([200, 40, 40, 255]),
+            Some(false) => status_icon([40, 180, 40, 255]),
+            None => status_icon([140, 140, 140, 255]),
+        };
+        let lock_item = IconMenuItem::new(lock_label, is_locked.is_some(), Some(lock_icon), None);
+        map.insert(
+            lock_item.id().clone(),
+            if is_locked == Some(true) {
+                Action::Unlock(Some(vehicle.vehicle_key.clone()))
+            } else {
+                Action::Lock(Some(vehicle.vehicle_key.clone()))
+            },
+        );
+        let _ = submenu.append(&lock_item);
+
+        let open_item = MenuItem::new("Open", true, None);
+        map.insert(open_item.id().clone(), Action::Open(vehicle.vehicle_key.clone()));
+        let _ = submenu.append(&open_item);
+
+        let _ = menu.append(&submenu);
+    }
+
+    *actions.lock().unwrap() = map;
+    menu
+}